@@ -22,8 +22,9 @@
 #![warn(missing_docs)]
 
 use jsonrpsee::RpcModule;
+use pallet_auto_id_rpc::{AutoId, AutoIdApiServer};
 use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
-use sc_client_api::{AuxStore, BlockBackend};
+use sc_client_api::{AuxStore, BlockBackend, BlockchainEvents};
 use sc_consensus_subspace::archiver::SegmentHeadersStore;
 use sc_consensus_subspace::notification::SubspaceNotificationStream;
 use sc_consensus_subspace::{
@@ -35,6 +36,7 @@ use sc_rpc_api::DenyUnsafe;
 use sc_rpc_spec_v2::chain_spec::{ChainSpec, ChainSpecApiServer};
 use sc_transaction_pool_api::TransactionPool;
 use sp_api::ProvideRuntimeApi;
+use sp_auto_id::AutoIdApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 use sp_consensus::SyncOracle;
@@ -87,6 +89,7 @@ pub fn create_full<C, P, SO, AS>(
 where
     C: ProvideRuntimeApi<Block>
         + BlockBackend<Block>
+        + BlockchainEvents<Block>
         + HeaderBackend<Block>
         + HeaderMetadata<Block, Error = BlockChainError>
         + Send
@@ -96,7 +99,8 @@ where
         + pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>
         + BlockBuilder<Block>
         + SubspaceApi<Block, FarmerPublicKey>
-        + ObjectsApi<Block>,
+        + ObjectsApi<Block>
+        + AutoIdApi<Block>,
     P: TransactionPool + 'static,
     SO: SyncOracle + Send + Sync + Clone + 'static,
     AS: AuxStore + Send + Sync + 'static,
@@ -124,6 +128,8 @@ where
 
     module.merge(System::new(client.clone(), pool, deny_unsafe).into_rpc())?;
     module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    module
+        .merge(AutoId::<_, Block>::new(client.clone(), subscription_executor.clone()).into_rpc())?;
 
     module.merge(
         SubspaceRpc::new(SubspaceRpcConfig {
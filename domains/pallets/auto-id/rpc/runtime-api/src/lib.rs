@@ -0,0 +1,67 @@
+// Copyright (C) 2024 Subspace Labs, Inc.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API for `pallet-auto-id`, consumed by `pallet-auto-id-rpc` so a light client can
+//! query the AutoId registry and its transparency-log proofs without trusting the node it asks.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use pallet_auto_id::{AutoId, Identifier};
+use sp_core::H256;
+
+sp_api::decl_runtime_apis! {
+    /// Runtime API exposing `pallet-auto-id`'s registry to RPC clients.
+    pub trait AutoIdApi {
+        /// Returns the `AutoId` registered under `identifier`, if any. Carries only
+        /// `identifier`'s own certificate and revocation status; use `certificate_chain` for
+        /// its resolved issuer chain, or `verify_chain` to check the whole chain is unrevoked.
+        fn get_certificate(identifier: Identifier) -> Option<AutoId>;
+
+        /// Verifies that `identifier`'s AutoId, and every issuer up its chain to a self-signed
+        /// root or an attested AutoId, is still registered and unrevoked.
+        fn verify_chain(identifier: Identifier) -> bool;
+
+        /// Returns `identifier`'s full issuer chain, starting with `identifier` itself and
+        /// ending at a self-signed root or an attested AutoId, or `None` if `identifier` isn't
+        /// registered. Each entry carries its own revocation status.
+        fn certificate_chain(identifier: Identifier) -> Option<Vec<AutoId>>;
+
+        /// Returns `(leaf_index, audit_path, root)` proving `identifier`'s registration is
+        /// included in the current transparency-log root.
+        fn inclusion_proof(identifier: Identifier) -> Option<(Identifier, Vec<H256>, H256)>;
+
+        /// Returns the consistency proof between the tree of the first `old_size` leaves and
+        /// the tree of the first `new_size` leaves.
+        fn consistency_proof(old_size: u64, new_size: u64) -> Option<Vec<H256>>;
+
+        /// The identifier that will be assigned to the next registered AutoId, i.e. the number
+        /// of AutoIds registered so far. Used by RPC subscribers to detect new registrations
+        /// between blocks without decoding this pallet's events.
+        fn next_identifier() -> Identifier;
+
+        /// Identifiers revoked or deactivated at or after `index` in the revocation log. Used
+        /// by RPC subscribers to detect new revocations between blocks the same way
+        /// `next_identifier` lets them detect new registrations.
+        fn revoked_since(index: u64) -> Vec<Identifier>;
+
+        /// The index that will be assigned to the next revocation log entry, i.e. the number of
+        /// revocations and deactivations recorded so far.
+        fn next_revocation_index() -> u64;
+    }
+}
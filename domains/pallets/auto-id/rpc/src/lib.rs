@@ -0,0 +1,261 @@
+// Copyright (C) 2024 Subspace Labs, Inc.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for querying `pallet-auto-id`'s AutoId registry and subscribing to
+//! registrations and revocations, backed by the `sp-auto-id` runtime API.
+
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::core::{async_trait, RpcResult};
+use jsonrpsee::proc_macros::rpc;
+use jsonrpsee::types::{ErrorObject, ErrorObjectOwned};
+use jsonrpsee::{PendingSubscriptionSink, SubscriptionMessage};
+use pallet_auto_id::Identifier;
+use sc_client_api::BlockchainEvents;
+use sc_rpc::SubscriptionTaskExecutor;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_auto_id::AutoIdApi as AutoIdRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::H256;
+use sp_runtime::traits::Block as BlockT;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+const RUNTIME_ERROR: i32 = 1;
+
+/// A change to the AutoId registry surfaced to `autoId_subscribeRegistrations` subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AutoIdRegistryEvent {
+    /// A new AutoId was registered under `identifier`.
+    Registered {
+        /// The newly registered AutoId's identifier.
+        identifier: Identifier,
+    },
+    /// An existing AutoId was revoked or deactivated.
+    Revoked {
+        /// The revoked or deactivated AutoId's identifier.
+        identifier: Identifier,
+    },
+}
+
+/// RPC methods exposed over `pallet-auto-id`'s registry.
+#[rpc(client, server)]
+pub trait AutoIdApi<BlockHash, AutoId> {
+    /// Returns the `AutoId` registered under `identifier`, if any, as of `at` (the best block
+    /// if omitted). Carries only `identifier`'s own certificate and revocation status; call
+    /// `autoId_getCertificateChain` for its resolved issuer chain.
+    #[method(name = "autoId_getCertificate")]
+    fn get_certificate(
+        &self,
+        identifier: Identifier,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<AutoId>>;
+
+    /// Returns `identifier`'s full issuer chain, starting with `identifier` itself and ending
+    /// at a self-signed root or an attested AutoId, as of `at`. Each entry carries its own
+    /// revocation status; see `autoId_verifyChain` to just check the whole chain is unrevoked.
+    #[method(name = "autoId_getCertificateChain")]
+    fn get_certificate_chain(
+        &self,
+        identifier: Identifier,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Vec<AutoId>>>;
+
+    /// Verifies `identifier`'s full issuer chain is registered and unrevoked, as of `at`.
+    #[method(name = "autoId_verifyChain")]
+    fn verify_chain(&self, identifier: Identifier, at: Option<BlockHash>) -> RpcResult<bool>;
+
+    /// Returns `(leaf_index, audit_path, root)` proving `identifier`'s registration is included
+    /// in the transparency-log root, as of `at`.
+    #[method(name = "autoId_getInclusionProof")]
+    fn get_inclusion_proof(
+        &self,
+        identifier: Identifier,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<(Identifier, Vec<H256>, H256)>>;
+
+    /// Returns the consistency proof between the tree of the first `old_size` leaves and the
+    /// tree of the first `new_size` leaves, as of `at`.
+    #[method(name = "autoId_getConsistencyProof")]
+    fn get_consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+        at: Option<BlockHash>,
+    ) -> RpcResult<Option<Vec<H256>>>;
+
+    /// Subscribes to AutoIds as they are registered or revoked/deactivated in new best blocks.
+    #[subscription(
+        name = "autoId_subscribeRegistrations" => "autoId_registration",
+        unsubscribe = "autoId_unsubscribeRegistrations",
+        item = AutoIdRegistryEvent
+    )]
+    async fn subscribe_registrations(&self);
+}
+
+/// Implements the [`AutoIdApiServer`] trait for querying `pallet-auto-id`'s registry over RPC.
+pub struct AutoId<C, Block> {
+    client: Arc<C>,
+    subscription_executor: SubscriptionTaskExecutor,
+    _marker: PhantomData<Block>,
+}
+
+impl<C, Block> AutoId<C, Block> {
+    /// Creates a new instance of the `AutoId` RPC helper.
+    pub fn new(client: Arc<C>, subscription_executor: SubscriptionTaskExecutor) -> Self {
+        Self {
+            client,
+            subscription_executor,
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn runtime_error(message: &str, err: impl core::fmt::Debug) -> ErrorObjectOwned {
+    ErrorObject::owned(RUNTIME_ERROR, message, Some(format!("{err:?}")))
+}
+
+#[async_trait]
+impl<C, Block> AutoIdApiServer<Block::Hash, pallet_auto_id::AutoId> for AutoId<C, Block>
+where
+    Block: BlockT,
+    C: ProvideRuntimeApi<Block>
+        + HeaderBackend<Block>
+        + BlockchainEvents<Block>
+        + Send
+        + Sync
+        + 'static,
+    C::Api: AutoIdRuntimeApi<Block>,
+{
+    fn get_certificate(
+        &self,
+        identifier: Identifier,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<pallet_auto_id::AutoId>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .get_certificate(at, identifier)
+            .map_err(|err| runtime_error("unable to query AutoId", err))
+    }
+
+    fn get_certificate_chain(
+        &self,
+        identifier: Identifier,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<Vec<pallet_auto_id::AutoId>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .certificate_chain(at, identifier)
+            .map_err(|err| runtime_error("unable to query AutoId chain", err))
+    }
+
+    fn verify_chain(&self, identifier: Identifier, at: Option<Block::Hash>) -> RpcResult<bool> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .verify_chain(at, identifier)
+            .map_err(|err| runtime_error("unable to verify AutoId chain", err))
+    }
+
+    fn get_inclusion_proof(
+        &self,
+        identifier: Identifier,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<(Identifier, Vec<H256>, H256)>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .inclusion_proof(at, identifier)
+            .map_err(|err| runtime_error("unable to produce inclusion proof", err))
+    }
+
+    fn get_consistency_proof(
+        &self,
+        old_size: u64,
+        new_size: u64,
+        at: Option<Block::Hash>,
+    ) -> RpcResult<Option<Vec<H256>>> {
+        let at = at.unwrap_or_else(|| self.client.info().best_hash);
+        self.client
+            .runtime_api()
+            .consistency_proof(at, old_size, new_size)
+            .map_err(|err| runtime_error("unable to produce consistency proof", err))
+    }
+
+    fn subscribe_registrations(&self, pending: PendingSubscriptionSink) {
+        let client = self.client.clone();
+        self.subscription_executor.spawn(
+            "auto-id-registrations-subscription",
+            Some("rpc"),
+            async move {
+                let Ok(sink) = pending.accept().await else {
+                    return;
+                };
+
+                let best_hash = client.info().best_hash;
+                let runtime_api = client.runtime_api();
+                let Ok(mut last_registered) = runtime_api.next_identifier(best_hash) else {
+                    return;
+                };
+                let Ok(mut last_revoked) = runtime_api.next_revocation_index(best_hash) else {
+                    return;
+                };
+
+                let mut import_notifications = client.import_notification_stream();
+                while let Some(notification) = import_notifications.next().await {
+                    if !notification.is_new_best {
+                        continue;
+                    }
+                    let runtime_api = client.runtime_api();
+
+                    let Ok(next_registered) = runtime_api.next_identifier(notification.hash) else {
+                        continue;
+                    };
+                    let Ok(next_revoked) = runtime_api.next_revocation_index(notification.hash)
+                    else {
+                        continue;
+                    };
+                    let Ok(revoked) = runtime_api.revoked_since(notification.hash, last_revoked)
+                    else {
+                        continue;
+                    };
+
+                    let events = (last_registered..next_registered)
+                        .map(|identifier| AutoIdRegistryEvent::Registered { identifier })
+                        .chain(
+                            revoked
+                                .into_iter()
+                                .map(|identifier| AutoIdRegistryEvent::Revoked { identifier }),
+                        );
+                    for event in events {
+                        let Ok(message) = SubscriptionMessage::from_json(&event) else {
+                            continue;
+                        };
+                        if sink.send(message).await.is_err() {
+                            return;
+                        }
+                    }
+                    last_registered = next_registered;
+                    last_revoked = next_revoked;
+                }
+            }
+            .boxed(),
+        );
+    }
+}
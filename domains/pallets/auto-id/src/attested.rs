@@ -0,0 +1,37 @@
+//! Registration of AutoIds backed by a TEE remote-attestation quote rather than an X.509
+//! chain, following the ROFL/enclave pattern where a measurement is the root of trust.
+
+use crate::{AttestedCertificate, RegisterAutoIdAttested, RegisterError};
+
+/// Verifies `req`'s attestation quote and returns the `AttestedCertificate` to be stored.
+///
+/// When `skip_verify` is set (the pallet's `SkipAttestationVerify` config, which must default
+/// to off in production), the quote signature is not checked and the claimed measurement and
+/// public key are trusted as-is, so a mock runtime can exercise registration, revocation and
+/// deactivation without generating a real quote.
+pub(crate) fn register(
+    req: RegisterAutoIdAttested,
+    skip_verify: bool,
+) -> Result<AttestedCertificate, RegisterError> {
+    let RegisterAutoIdAttested {
+        report,
+        measurement,
+        public_key,
+    } = req;
+
+    if !skip_verify {
+        let valid =
+            sp_certificate_registry::verify_attestation_quote(&report, &measurement, &public_key)
+                .ok_or(RegisterError::InvalidAttestation)?;
+        if !valid {
+            return Err(RegisterError::InvalidAttestation);
+        }
+    }
+
+    Ok(AttestedCertificate {
+        measurement,
+        report,
+        public_key,
+        revoked: false,
+    })
+}
@@ -0,0 +1,703 @@
+// Copyright (C) 2023 Subspace Labs, Inc.
+// SPDX-License-Identifier: GPL-3.0-or-later
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Pallet auto-id is responsible for registering, revoking and deactivating X.509-backed or
+//! TEE-attested on-chain identities ("AutoIds"). An AutoId is registered from a certificate
+//! (either a self-signed root or a leaf signed by an already-registered issuer) or from a
+//! remote-attestation quote, and every subsequent
+//! action against it (revocation, deactivation) is authorized by a signature over the
+//! AutoId's identifier, verifiable with the credential stored for it.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(test)]
+mod tests;
+
+mod attested;
+mod merkle;
+mod x509;
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use codec::{Decode, Encode};
+use scale_info::TypeInfo;
+use sp_certificate_registry::DerVec;
+
+/// Identifier of an AutoId, assigned sequentially at registration.
+pub type Identifier = u64;
+
+/// DER-encoded serial number of an X.509 certificate.
+pub type Serial = DerVec;
+
+/// A signature over some preimage, together with the algorithm it was produced with.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct Signature {
+    /// DER-encoded `AlgorithmIdentifier` used to produce `value`.
+    pub signature_algorithm: DerVec,
+    /// Raw signature bytes.
+    pub value: Vec<u8>,
+}
+
+/// An X.509 certificate as stored on-chain: the raw `TBSCertificate` bytes plus the signature
+/// that was produced over them.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct CertificateRaw {
+    /// DER-encoded `TBSCertificate`.
+    pub certificate: DerVec,
+    /// DER-encoded `AlgorithmIdentifier` of the signature.
+    pub signature_algorithm: DerVec,
+    /// Raw signature bytes over `certificate`.
+    pub signature: Vec<u8>,
+}
+
+/// Parameters required to register a new AutoId from an X.509 certificate.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum RegisterAutoIdX509 {
+    /// A self-signed root certificate, acting as an issuer of further AutoIds.
+    Root {
+        /// DER-encoded `TBSCertificate`.
+        certificate: DerVec,
+        /// DER-encoded `AlgorithmIdentifier` of the signature.
+        signature_algorithm: DerVec,
+        /// Raw signature bytes, verified against the certificate's own public key.
+        signature: Vec<u8>,
+    },
+    /// A leaf certificate, signed by the issuer identified by `issuer_id`.
+    Leaf {
+        /// The AutoId of the issuer that signed this certificate.
+        issuer_id: Identifier,
+        /// DER-encoded `TBSCertificate`.
+        certificate: DerVec,
+        /// DER-encoded `AlgorithmIdentifier` of the signature.
+        signature_algorithm: DerVec,
+        /// Raw signature bytes, verified against the issuer's public key.
+        signature: Vec<u8>,
+    },
+}
+
+/// Parameters required to register an AutoId backed by a TEE remote-attestation quote
+/// instead of an X.509 chain, following the ROFL/enclave pattern where a measurement is the
+/// root of trust.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct RegisterAutoIdAttested {
+    /// The raw remote-attestation report/quote bytes.
+    pub report: DerVec,
+    /// The enclave measurement (MRENCLAVE) the report attests to.
+    pub measurement: [u8; 32],
+    /// The public key embedded in the report's user-data, used to verify future signatures
+    /// from this AutoId.
+    pub public_key: DerVec,
+}
+
+/// Parameters required to register a new AutoId.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum RegisterAutoId {
+    /// Registration backed by an X.509 certificate.
+    X509(RegisterAutoIdX509),
+    /// Registration backed by a TEE remote-attestation quote.
+    Attested(RegisterAutoIdAttested),
+}
+
+/// An AutoId backed by a TEE remote-attestation quote, as stored on-chain.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct AttestedCertificate {
+    /// The enclave measurement (MRENCLAVE) this AutoId was registered with.
+    pub measurement: [u8; 32],
+    /// The raw remote-attestation report/quote bytes this AutoId was registered with.
+    pub report: DerVec,
+    /// The public key embedded in the attestation report, used to verify signatures made by
+    /// this AutoId.
+    pub public_key: DerVec,
+    /// Whether this AutoId has been revoked.
+    pub revoked: bool,
+}
+
+/// The credential backing an AutoId, and its revocation state.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub enum CertificateAutoId {
+    X509(X509Certificate),
+    Attested(AttestedCertificate),
+}
+
+impl CertificateAutoId {
+    /// Whether the underlying certificate has been revoked.
+    pub fn is_revoked(&self) -> bool {
+        match self {
+            Self::X509(cert) => cert.revoked,
+            Self::Attested(cert) => cert.revoked,
+        }
+    }
+
+    fn revoke(&mut self) {
+        match self {
+            Self::X509(cert) => cert.revoked = true,
+            Self::Attested(cert) => cert.revoked = true,
+        }
+    }
+
+    /// The DER-encoded public key used to verify signatures made by this AutoId.
+    fn subject_public_key_info(&self) -> &DerVec {
+        match self {
+            Self::X509(cert) => &cert.subject_public_key_info,
+            Self::Attested(cert) => &cert.public_key,
+        }
+    }
+
+    /// The raw bytes hashed into the transparency log leaf for this AutoId: the
+    /// `TBSCertificate` for an X.509 AutoId, or the attestation report for an attested one.
+    fn transparency_log_preimage(&self) -> &[u8] {
+        match self {
+            Self::X509(cert) => cert.certificate.as_ref(),
+            Self::Attested(cert) => cert.report.as_ref(),
+        }
+    }
+}
+
+/// Reasons registration (X.509 or attested) can fail, mapped to `pallet::Error` by the caller.
+pub(crate) enum RegisterError {
+    InvalidCertificate,
+    InvalidSignature,
+    UnknownIssuer,
+    IssuerNotX509,
+    NotACertificateAuthority,
+    PathLengthExceeded,
+    MissingKeyCertSign,
+    NameConstraintViolation,
+    InvalidAttestation,
+}
+
+pub use sp_certificate_registry::{GeneralName, NameConstraints};
+
+/// On-chain representation of a registered X.509 certificate.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct X509Certificate {
+    /// The issuer's AutoId, if this certificate is a leaf.
+    pub issuer_id: Option<Identifier>,
+    /// DER-encoded `TBSCertificate`.
+    pub certificate: DerVec,
+    /// DER-encoded serial number, as found in the certificate.
+    pub serial: Serial,
+    /// DER-encoded Subject Distinguished Name, used to match this AutoId against CRLs it
+    /// issues and against name constraints asserted by its own issuer.
+    pub subject: DerVec,
+    /// Subject public key info, DER-encoded, used to verify signatures made by this AutoId.
+    pub subject_public_key_info: DerVec,
+    /// Whether the `BasicConstraints` extension asserted `cA: TRUE`, i.e. whether this AutoId
+    /// is itself entitled to sign further `Leaf` AutoIds.
+    pub is_ca: bool,
+    /// Whether the `KeyUsage` extension asserted `keyCertSign`. Checked together with `is_ca`
+    /// against an issuer before it is allowed to sign a further `Leaf` AutoId.
+    pub key_cert_sign: bool,
+    /// The `BasicConstraints` `pathLenConstraint`, if any. For a `Leaf`, capped at its issuer's
+    /// value less one, so it counts down by one with every level of subordinate CA.
+    pub path_len_constraint: Option<u32>,
+    /// The `nameConstraints` this AutoId imposes on any `Leaf` it signs.
+    pub name_constraints: NameConstraints,
+    /// Whether the certificate has been revoked via `revoke_certificate` or
+    /// `revoke_certificates`.
+    pub revoked: bool,
+}
+
+/// An AutoId as stored on-chain.
+#[derive(Debug, Encode, Decode, TypeInfo, Clone, PartialEq, Eq)]
+pub struct AutoId {
+    /// The identity's credential.
+    pub certificate: CertificateAutoId,
+}
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+    use super::{AutoId, CertificateAutoId, Identifier, RegisterAutoId, Serial, Signature};
+    use frame_support::pallet_prelude::*;
+    use frame_support::traits::Time;
+    use frame_system::pallet_prelude::*;
+    use sp_core::H256;
+    use sp_runtime::traits::Hash;
+
+    #[pallet::config]
+    pub trait Config: frame_system::Config {
+        /// Overarching event type.
+        type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+        /// Source of the current time, used to validate certificate and CRL validity windows.
+        type Time: Time<Moment = subspace_runtime_primitives::Moment>;
+        /// Dev/test escape hatch that skips remote-attestation quote verification for
+        /// `RegisterAutoId::Attested`, trusting the claimed measurement and public key as-is.
+        /// Must be `ConstBool<false>` in production; only a mock runtime should set it true,
+        /// to exercise attested registration without a real quote.
+        #[pallet::constant]
+        type SkipAttestationVerify: Get<bool>;
+    }
+
+    #[pallet::pallet]
+    pub struct Pallet<T>(_);
+
+    /// Registered AutoIds, keyed by their identifier.
+    #[pallet::storage]
+    pub type AutoIds<T: Config> = StorageMap<_, Identity, Identifier, AutoId, OptionQuery>;
+
+    /// The identifier that will be assigned to the next registered AutoId.
+    #[pallet::storage]
+    pub type NextAutoIdIdentifier<T: Config> = StorageValue<_, Identifier, ValueQuery>;
+
+    /// Maps an issuer's `(issuer_id, certificate serial)` to the AutoId it was registered
+    /// under, so that a CRL's serial numbers can be resolved back to AutoIds without a scan.
+    #[pallet::storage]
+    pub type CertificateSerials<T: Config> =
+        StorageMap<_, Blake2_128Concat, (Identifier, Serial), Identifier, OptionQuery>;
+
+    /// Completed nodes of the transparency-log Merkle tree, keyed by `(level, index)`: level 0
+    /// is leaf hashes, one per registered AutoId (whose `Identifier` doubles as its leaf index,
+    /// since both start at zero and advance in lock-step), and each level above holds the root
+    /// of a completed, perfectly-sized subtree spanning `2^level` leaves starting at
+    /// `index * 2^level`. Populated incrementally as AutoIds register, so inclusion and
+    /// consistency proofs read O(log n) stored nodes instead of rebuilding the tree from every
+    /// leaf on every call.
+    #[pallet::storage]
+    pub type RegistryNodes<T: Config> =
+        StorageMap<_, Identity, (u32, Identifier), H256, OptionQuery>;
+
+    /// The current root of the Merkle tree over `RegistryNodes`, anchored in every block
+    /// header via this pallet's storage root.
+    #[pallet::storage]
+    pub type RegistryRoot<T: Config> = StorageValue<_, H256, ValueQuery>;
+
+    /// A monotonically increasing log of identifiers revoked or deactivated (via
+    /// `revoke_certificate`, `revoke_certificates`, or `deactivate_auto_id`), keyed by the
+    /// index it was recorded at. Lets RPC subscribers diff new revocations between blocks the
+    /// same way `NextAutoIdIdentifier` lets them diff new registrations, without decoding
+    /// events.
+    #[pallet::storage]
+    pub type RevocationLog<T: Config> = StorageMap<_, Identity, u64, Identifier, OptionQuery>;
+
+    /// The index that will be assigned to the next entry in `RevocationLog`.
+    #[pallet::storage]
+    pub type NextRevocationIndex<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    #[pallet::event]
+    #[pallet::generate_deposit(pub(super) fn deposit_event)]
+    pub enum Event<T: Config> {
+        /// A new AutoId was registered.
+        NewAutoIdRegistered(Identifier),
+        /// An AutoId's certificate was revoked.
+        CertificateRevoked(Identifier),
+        /// An AutoId was deactivated.
+        AutoIdDeactivated(Identifier),
+    }
+
+    #[pallet::error]
+    pub enum Error<T> {
+        /// The supplied certificate could not be parsed as a valid X.509 `TBSCertificate`.
+        InvalidCertificate,
+        /// The supplied signature does not verify against the expected public key.
+        InvalidSignature,
+        /// A `Leaf` registration or a CRL referenced an issuer AutoId that does not exist.
+        UnknownIssuer,
+        /// The AutoId referenced by a call does not exist.
+        AutoIdNotFound,
+        /// The certificate backing this AutoId has already been revoked.
+        CertificateRevoked,
+        /// The certificate's validity window does not contain the current time.
+        ExpiredCertificate,
+        /// The supplied bytes could not be parsed as a valid X.509 `TBSCertList`.
+        InvalidCrl,
+        /// The CRL's issuer does not match the subject DN stored for the given AutoId.
+        CrlIssuerMismatch,
+        /// The current time does not fall within the CRL's `thisUpdate`/`nextUpdate` window.
+        CrlNotInValidityWindow,
+        /// A `Root` registration's certificate, or a `Leaf`'s issuer, does not assert
+        /// `BasicConstraints { cA: TRUE }`.
+        NotACertificateAuthority,
+        /// Registering this AutoId as a CA would exceed its issuer's `pathLenConstraint`.
+        PathLengthExceeded,
+        /// A certificate entitled to sign further AutoIds must assert the `keyCertSign`
+        /// `KeyUsage` bit.
+        MissingKeyCertSign,
+        /// A `Leaf`'s subject or SubjectAltName names fall outside its issuer's
+        /// `nameConstraints`.
+        NameConstraintViolation,
+        /// A `Leaf` or a CRL was submitted against an issuer that isn't backed by an X.509
+        /// certificate.
+        IssuerNotX509,
+        /// The supplied remote-attestation quote did not verify against the claimed
+        /// measurement and public key.
+        InvalidAttestation,
+    }
+
+    impl<T: Config> Pallet<T> {
+        /// Parses and validates `req`, returning the `AutoId` to be stored under `identifier`.
+        fn do_register_auto_id(
+            identifier: Identifier,
+            req: RegisterAutoId,
+        ) -> Result<AutoId, DispatchError> {
+            match req {
+                RegisterAutoId::X509(x509_req) => {
+                    let issuer = match &x509_req {
+                        super::RegisterAutoIdX509::Leaf { issuer_id, .. } => {
+                            Some(AutoIds::<T>::get(issuer_id).ok_or(Error::<T>::UnknownIssuer)?)
+                        }
+                        super::RegisterAutoIdX509::Root { .. } => None,
+                    };
+                    let certificate = super::x509::register(identifier, x509_req, issuer)
+                        .map_err(Self::map_register_error)?;
+
+                    if let Some(issuer_id) = certificate.issuer_id {
+                        CertificateSerials::<T>::insert(
+                            (
+                                issuer_id,
+                                super::x509::normalize_serial(&certificate.serial),
+                            ),
+                            identifier,
+                        );
+                    }
+
+                    Ok(AutoId {
+                        certificate: CertificateAutoId::X509(certificate),
+                    })
+                }
+                RegisterAutoId::Attested(attested_req) => {
+                    let certificate =
+                        super::attested::register(attested_req, T::SkipAttestationVerify::get())
+                            .map_err(Self::map_register_error)?;
+
+                    Ok(AutoId {
+                        certificate: CertificateAutoId::Attested(certificate),
+                    })
+                }
+            }
+        }
+
+        /// Verifies `signature` over `preimage` against the public key backing `auto_id`,
+        /// using the host-function verification path shared with registration.
+        fn verify_signature(
+            auto_id: &AutoId,
+            preimage: alloc::vec::Vec<u8>,
+            signature: &Signature,
+        ) -> DispatchResult {
+            let valid = sp_certificate_registry::verify_signature(
+                auto_id.certificate.subject_public_key_info(),
+                &signature.signature_algorithm,
+                &preimage,
+                &signature.value,
+            )
+            .ok_or(Error::<T>::InvalidSignature)?;
+            ensure!(valid, Error::<T>::InvalidSignature);
+            Ok(())
+        }
+
+        /// Maps a parsing/verification failure from the `x509`/`attested` modules to this
+        /// pallet's `Error`.
+        fn map_register_error(error: super::RegisterError) -> Error<T> {
+            use super::RegisterError;
+            match error {
+                RegisterError::InvalidCertificate => Error::<T>::InvalidCertificate,
+                RegisterError::InvalidSignature => Error::<T>::InvalidSignature,
+                RegisterError::UnknownIssuer => Error::<T>::UnknownIssuer,
+                RegisterError::IssuerNotX509 => Error::<T>::IssuerNotX509,
+                RegisterError::NotACertificateAuthority => Error::<T>::NotACertificateAuthority,
+                RegisterError::PathLengthExceeded => Error::<T>::PathLengthExceeded,
+                RegisterError::MissingKeyCertSign => Error::<T>::MissingKeyCertSign,
+                RegisterError::NameConstraintViolation => Error::<T>::NameConstraintViolation,
+                RegisterError::InvalidAttestation => Error::<T>::InvalidAttestation,
+            }
+        }
+
+        /// Returns the `AutoId` registered under `identifier`, if any. Backs the `sp-auto-id`
+        /// runtime API's `get_certificate`.
+        pub fn get_certificate(identifier: Identifier) -> Option<AutoId> {
+            AutoIds::<T>::get(identifier)
+        }
+
+        /// Walks `identifier`'s issuer chain up to a self-signed root or an attested AutoId,
+        /// returning `true` only if every AutoId on the way, including `identifier` itself, is
+        /// still registered and unrevoked. Backs the `sp-auto-id` runtime API's `verify_chain`.
+        pub fn verify_chain(identifier: Identifier) -> bool {
+            let mut current = identifier;
+            loop {
+                let Some(auto_id) = AutoIds::<T>::get(current) else {
+                    return false;
+                };
+                if auto_id.certificate.is_revoked() {
+                    return false;
+                }
+                match &auto_id.certificate {
+                    CertificateAutoId::X509(cert) => match cert.issuer_id {
+                        Some(issuer_id) => current = issuer_id,
+                        None => return true,
+                    },
+                    CertificateAutoId::Attested(_) => return true,
+                }
+            }
+        }
+
+        /// The identifier that will be assigned to the next registered AutoId. Backs the
+        /// `sp-auto-id` runtime API's `next_identifier`, letting RPC subscribers detect new
+        /// registrations between blocks without decoding this pallet's events.
+        pub fn next_identifier() -> Identifier {
+            NextAutoIdIdentifier::<T>::get()
+        }
+
+        /// `identifier`'s full issuer chain, starting with `identifier` itself and ending at a
+        /// self-signed root or an attested AutoId, or `None` if `identifier` isn't registered.
+        /// Backs the `sp-auto-id` runtime API's `certificate_chain`, so a caller can get the
+        /// full chain `get_certificate` alone omits in one round trip rather than composing it
+        /// from repeated `get_certificate` calls themselves.
+        pub fn certificate_chain(identifier: Identifier) -> Option<alloc::vec::Vec<AutoId>> {
+            let mut chain = alloc::vec::Vec::new();
+            let mut current = identifier;
+            loop {
+                let auto_id = AutoIds::<T>::get(current)?;
+                let next = match &auto_id.certificate {
+                    CertificateAutoId::X509(cert) => cert.issuer_id,
+                    CertificateAutoId::Attested(_) => None,
+                };
+                chain.push(auto_id);
+                match next {
+                    Some(issuer_id) => current = issuer_id,
+                    None => return Some(chain),
+                }
+            }
+        }
+
+        /// Records `identifier` in `RevocationLog`, so RPC subscribers can be told about
+        /// revocations the same way they're told about registrations.
+        fn record_revocation(identifier: Identifier) {
+            let index = NextRevocationIndex::<T>::get();
+            RevocationLog::<T>::insert(index, identifier);
+            NextRevocationIndex::<T>::put(index + 1);
+        }
+
+        /// Entries of `RevocationLog` from `index` onward. Backs the `sp-auto-id` runtime API's
+        /// `revoked_since`, letting RPC subscribers detect new revocations between blocks
+        /// without decoding this pallet's events.
+        pub fn revoked_since(index: u64) -> alloc::vec::Vec<Identifier> {
+            (index..NextRevocationIndex::<T>::get())
+                .filter_map(RevocationLog::<T>::get)
+                .collect()
+        }
+
+        /// The index that will be assigned to the next `RevocationLog` entry. Backs the
+        /// `sp-auto-id` runtime API's `next_revocation_index`.
+        pub fn next_revocation_index() -> u64 {
+            NextRevocationIndex::<T>::get()
+        }
+
+        /// A `stored(level, index)` callback for `super::merkle`'s node-cache-aware functions,
+        /// backed directly by `RegistryNodes`.
+        fn stored_node(level: u32, index: Identifier) -> Option<H256> {
+            RegistryNodes::<T>::get((level, index))
+        }
+
+        /// Appends `identifier`'s registration to the transparency log: stores its leaf hash,
+        /// folds it into every completed perfect-subtree node it now completes (the standard
+        /// incremental/append-only Merkle tree carry), and recomputes `RegistryRoot` from those
+        /// stored nodes rather than the full leaf set.
+        fn append_registry_leaf(identifier: Identifier, tbs_certificate: &[u8]) {
+            let tbs_certificate_hash = T::Hashing::hash(tbs_certificate);
+            let mut preimage = identifier.encode();
+            preimage.extend_from_slice(tbs_certificate_hash.as_ref());
+            let leaf = T::Hashing::hash(&preimage);
+
+            RegistryNodes::<T>::insert((0u32, identifier), leaf);
+
+            let mut node = leaf;
+            let mut level = 0u32;
+            let mut index = identifier;
+            while index % 2 == 1 {
+                let sibling = RegistryNodes::<T>::get((level, index - 1)).unwrap_or_default();
+                node = super::merkle::hash_node::<T::Hashing>(&sibling, &node);
+                index /= 2;
+                level += 1;
+                RegistryNodes::<T>::insert((level, index), node);
+            }
+
+            RegistryRoot::<T>::put(super::merkle::root::<T::Hashing>(
+                identifier + 1,
+                &Self::stored_node,
+            ));
+        }
+
+        /// Returns `(leaf_index, audit_path, root)` proving `identifier`'s registration is
+        /// included in `RegistryRoot`, verifiable by recomputing the root from the leaf and
+        /// the audit path.
+        pub fn auto_id_inclusion_proof(
+            identifier: Identifier,
+        ) -> Option<(Identifier, alloc::vec::Vec<H256>, H256)> {
+            let leaf_count = NextAutoIdIdentifier::<T>::get();
+            if identifier >= leaf_count {
+                return None;
+            }
+
+            let path =
+                super::merkle::audit_path::<T::Hashing>(identifier, leaf_count, &Self::stored_node);
+            Some((identifier, path, RegistryRoot::<T>::get()))
+        }
+
+        /// Returns the sibling set proving the registry only grew by append between
+        /// `old_size` and `new_size` (the current tree size), mirroring RFC 6962 consistency
+        /// proofs. Returns `None` if `old_size` is out of range for the current tree.
+        pub fn consistency_proof(
+            old_size: Identifier,
+            new_size: Identifier,
+        ) -> Option<alloc::vec::Vec<H256>> {
+            let leaf_count = NextAutoIdIdentifier::<T>::get();
+            if old_size > new_size || new_size != leaf_count {
+                return None;
+            }
+
+            Some(super::merkle::consistency_proof::<T::Hashing>(
+                old_size,
+                new_size,
+                &Self::stored_node,
+            ))
+        }
+    }
+
+    #[pallet::call]
+    impl<T: Config> Pallet<T> {
+        /// Register a new AutoId from a `Root`/`Leaf` X.509 certificate or a TEE
+        /// remote-attestation quote.
+        #[pallet::call_index(0)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn register_auto_id(origin: OriginFor<T>, req: RegisterAutoId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let identifier = NextAutoIdIdentifier::<T>::get();
+            let auto_id = Self::do_register_auto_id(identifier, req)?;
+            Self::append_registry_leaf(identifier, auto_id.certificate.transparency_log_preimage());
+
+            AutoIds::<T>::insert(identifier, auto_id);
+            NextAutoIdIdentifier::<T>::put(identifier + 1);
+
+            Self::deposit_event(Event::<T>::NewAutoIdRegistered(identifier));
+            Ok(())
+        }
+
+        /// Revoke a single AutoId's certificate. Authorized by a signature over the
+        /// identifier's SCALE encoding, verifiable with the AutoId's own credential.
+        #[pallet::call_index(1)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_certificate(
+            origin: OriginFor<T>,
+            identifier: Identifier,
+            signature: Signature,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            AutoIds::<T>::try_mutate(identifier, |maybe_auto_id| -> DispatchResult {
+                let auto_id = maybe_auto_id.as_mut().ok_or(Error::<T>::AutoIdNotFound)?;
+                ensure!(
+                    !auto_id.certificate.is_revoked(),
+                    Error::<T>::CertificateRevoked
+                );
+                Self::verify_signature(auto_id, identifier.encode(), &signature)?;
+                auto_id.certificate.revoke();
+                Ok(())
+            })?;
+
+            Self::record_revocation(identifier);
+            Self::deposit_event(Event::<T>::CertificateRevoked(identifier));
+            Ok(())
+        }
+
+        /// Deactivate an AutoId entirely, removing it from storage. Authorized the same way
+        /// as `revoke_certificate`.
+        #[pallet::call_index(2)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn deactivate_auto_id(
+            origin: OriginFor<T>,
+            identifier: Identifier,
+            signature: Signature,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let auto_id = AutoIds::<T>::get(identifier).ok_or(Error::<T>::AutoIdNotFound)?;
+            Self::verify_signature(&auto_id, identifier.encode(), &signature)?;
+            AutoIds::<T>::remove(identifier);
+
+            Self::record_revocation(identifier);
+            Self::deposit_event(Event::<T>::AutoIdDeactivated(identifier));
+            Ok(())
+        }
+
+        /// Revoke every certificate named in a CRL issued by `issuer_id`, in one extrinsic.
+        ///
+        /// `crl_der` carries the DER-encoded `TBSCertList` (mirroring how `register_auto_id`
+        /// carries a bare `TBSCertificate`), and `signature` is verified over it against the
+        /// issuer's own public key. Serials the CRL names that were not issued by `issuer_id`
+        /// are ignored, and an AutoId already marked revoked is left untouched, so resubmitting
+        /// an overlapping or previously-seen CRL is a no-op rather than an error.
+        #[pallet::call_index(3)]
+        #[pallet::weight(Weight::from_parts(10_000, 0))]
+        pub fn revoke_certificates(
+            origin: OriginFor<T>,
+            issuer_id: Identifier,
+            crl_der: sp_certificate_registry::DerVec,
+            signature: Signature,
+        ) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let issuer = AutoIds::<T>::get(issuer_id).ok_or(Error::<T>::UnknownIssuer)?;
+            let CertificateAutoId::X509(issuer_cert) = &issuer.certificate else {
+                return Err(Error::<T>::IssuerNotX509.into());
+            };
+
+            let crl = super::x509::parse_and_verify_crl(issuer_cert, &crl_der, &signature)
+                .map_err(|err| match err {
+                    super::RegisterError::InvalidSignature => Error::<T>::InvalidSignature,
+                    _ => Error::<T>::InvalidCrl,
+                })?;
+            ensure!(
+                crl.issuer == issuer_cert.subject,
+                Error::<T>::CrlIssuerMismatch
+            );
+
+            let now = T::Time::now();
+            ensure!(crl.this_update <= now, Error::<T>::CrlNotInValidityWindow);
+            if let Some(next_update) = crl.next_update {
+                ensure!(now <= next_update, Error::<T>::CrlNotInValidityWindow);
+            }
+
+            for serial in crl.revoked_serials {
+                let Some(identifier) = CertificateSerials::<T>::get((issuer_id, serial)) else {
+                    // Not issued by this issuer; nothing to do.
+                    continue;
+                };
+
+                let revoked = AutoIds::<T>::mutate(identifier, |maybe_auto_id| {
+                    let Some(auto_id) = maybe_auto_id else {
+                        return false;
+                    };
+                    if auto_id.certificate.is_revoked() {
+                        // Already revoked, re-submitting an overlapping CRL is a no-op.
+                        return false;
+                    }
+                    auto_id.certificate.revoke();
+                    true
+                });
+
+                if revoked {
+                    Self::record_revocation(identifier);
+                    Self::deposit_event(Event::<T>::CertificateRevoked(identifier));
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! A Merkle tree hash over registration events, following the tree hashing, audit path and
+//! consistency proof algorithms of RFC 6962 §2.1 ("Certificate Transparency"), so a light
+//! client can verify registry membership and append-only growth without trusting the node
+//! that answers the query.
+//!
+//! Every function here takes a `stored(level, index)` lookup rather than a materialized leaf
+//! array: level 0 is individual leaf hashes, and each level above holds the root of a
+//! completed, perfectly-sized subtree of `2^level` leaves starting at leaf `index * 2^level`.
+//! Every range RFC 6962's recursive left/right split asks for is either exactly one of these
+//! completed subtrees (a single `stored` lookup) or splits further into two such ranges, so a
+//! query only recomputes along the still-growing boundary of the tree, which is at most
+//! `O(log n)` deep.
+
+use alloc::vec;
+use alloc::vec::Vec;
+use sp_core::H256;
+use sp_runtime::traits::Hash;
+
+/// `MTH` from RFC 6962 §2.1: the Merkle Tree Hash of the first `leaf_count` leaves.
+pub(crate) fn root<H: Hash<Output = H256>>(
+    leaf_count: u64,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> H256 {
+    if leaf_count == 0 {
+        return H256::zero();
+    }
+    range_root::<H>(0, leaf_count, stored)
+}
+
+/// `PATH(m, D[n])` from RFC 6962 §2.1.1: the audit path proving leaf `leaf_index` is included
+/// among the first `leaf_count` leaves.
+pub(crate) fn audit_path<H: Hash<Output = H256>>(
+    leaf_index: u64,
+    leaf_count: u64,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> Vec<H256> {
+    audit_path_range::<H>(leaf_index, 0, leaf_count, stored)
+}
+
+fn audit_path_range<H: Hash<Output = H256>>(
+    leaf_index: u64,
+    offset: u64,
+    size: u64,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> Vec<H256> {
+    if size <= 1 {
+        return Vec::new();
+    }
+
+    let k = largest_power_of_two_less_than(size);
+    if leaf_index - offset < k {
+        let mut path = audit_path_range::<H>(leaf_index, offset, k, stored);
+        path.push(range_root::<H>(offset + k, size - k, stored));
+        path
+    } else {
+        let mut path = audit_path_range::<H>(leaf_index, offset + k, size - k, stored);
+        path.push(range_root::<H>(offset, k, stored));
+        path
+    }
+}
+
+/// `PROOF(m, D[n])` from RFC 6962 §2.1.2: the consistency proof between the tree of the first
+/// `old_size` leaves and the tree of the first `new_size` leaves.
+pub(crate) fn consistency_proof<H: Hash<Output = H256>>(
+    old_size: u64,
+    new_size: u64,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> Vec<H256> {
+    if old_size == 0 || old_size == new_size {
+        return Vec::new();
+    }
+    subproof::<H>(old_size, 0, new_size, true, stored)
+}
+
+fn subproof<H: Hash<Output = H256>>(
+    m: u64,
+    offset: u64,
+    size: u64,
+    b: bool,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> Vec<H256> {
+    if m == size {
+        return if b {
+            Vec::new()
+        } else {
+            vec![range_root::<H>(offset, size, stored)]
+        };
+    }
+
+    let k = largest_power_of_two_less_than(size);
+    if m <= k {
+        let mut proof = subproof::<H>(m, offset, k, b, stored);
+        proof.push(range_root::<H>(offset + k, size - k, stored));
+        proof
+    } else {
+        let mut proof = subproof::<H>(m - k, offset + k, size - k, false, stored);
+        proof.push(range_root::<H>(offset, k, stored));
+        proof
+    }
+}
+
+/// The root hash of the `size` leaves starting at `offset`. Whenever `[offset, offset + size)`
+/// is exactly a completed perfect subtree (`size` a power of two, `offset` a multiple of it),
+/// this is a single `stored` lookup; otherwise it recurses with the same left/right split
+/// `root`/`audit_path`/`consistency_proof` use.
+fn range_root<H: Hash<Output = H256>>(
+    offset: u64,
+    size: u64,
+    stored: &impl Fn(u32, u64) -> Option<H256>,
+) -> H256 {
+    if size == 1 {
+        return stored(0, offset).unwrap_or_default();
+    }
+    if size.is_power_of_two() && offset % size == 0 {
+        if let Some(node) = stored(size.trailing_zeros(), offset / size) {
+            return node;
+        }
+    }
+
+    let k = largest_power_of_two_less_than(size);
+    let left = range_root::<H>(offset, k, stored);
+    let right = range_root::<H>(offset + k, size - k, stored);
+    hash_node::<H>(&left, &right)
+}
+
+/// RFC 6962's `k`: the largest power of two strictly less than `n` (for `n > 1`).
+fn largest_power_of_two_less_than(n: u64) -> u64 {
+    debug_assert!(n > 1);
+    let mut k = 1u64;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+pub(crate) fn hash_node<H: Hash<Output = H256>>(left: &H256, right: &H256) -> H256 {
+    let mut preimage = Vec::with_capacity(65);
+    preimage.push(0x01);
+    preimage.extend_from_slice(left.as_bytes());
+    preimage.extend_from_slice(right.as_bytes());
+    H::hash(&preimage)
+}
@@ -1,10 +1,11 @@
 use crate::pallet::{AutoIds, NextAutoIdIdentifier};
 use crate::{
-    self as pallet_auto_id, Identifier, Pallet, RegisterAutoId, RegisterAutoIdX509, Signature,
+    self as pallet_auto_id, Identifier, Pallet, RegisterAutoId, RegisterAutoIdAttested,
+    RegisterAutoIdX509, Signature,
 };
 use codec::Encode;
 use frame_support::dispatch::RawOrigin;
-use frame_support::traits::{ConstU16, ConstU32, ConstU64, Time};
+use frame_support::traits::{ConstBool, ConstU16, ConstU32, ConstU64, Time};
 use pem::parse;
 use ring::rand::SystemRandom;
 use ring::signature::RsaKeyPair;
@@ -40,6 +41,9 @@ impl Time for MockTime {
 impl pallet_auto_id::Config for Test {
     type RuntimeEvent = RuntimeEvent;
     type Time = MockTime;
+    // Lets tests exercise `RegisterAutoId::Attested` without a real attestation quote; a
+    // production runtime must use `ConstBool<false>` instead.
+    type SkipAttestationVerify = ConstBool<true>;
 }
 
 impl frame_system::Config for Test {
@@ -155,6 +159,26 @@ fn register_leaf_auto_id(issuer_auto_id: Identifier) -> Identifier {
     auto_id_identifier
 }
 
+fn register_attested_auto_id() -> Identifier {
+    let issuer_cert = include_bytes!("../res/issuer.cert.der").to_vec();
+    let (_, cert) = x509_parser::certificate::X509Certificate::from_der(&issuer_cert).unwrap();
+    let public_key = cert.tbs_certificate.subject_pki.raw.to_vec().into();
+
+    let auto_id_identifier = NextAutoIdIdentifier::<Test>::get();
+    Pallet::<Test>::register_auto_id(
+        RawOrigin::Signed(1).into(),
+        RegisterAutoId::Attested(RegisterAutoIdAttested {
+            report: b"mock attestation report".to_vec().into(),
+            measurement: [7u8; 32],
+            public_key,
+        }),
+    )
+    .unwrap();
+
+    assert_eq!(NextAutoIdIdentifier::<Test>::get(), auto_id_identifier + 1);
+    auto_id_identifier
+}
+
 fn sign_preimage(data: Vec<u8>) -> Signature {
     let priv_key_pem = include_str!("../res/private.issuer.pem");
     let priv_key_der = parse(priv_key_pem).unwrap().contents().to_vec();
@@ -226,3 +250,67 @@ fn test_deactivate_auto_id() {
         assert!(AutoIds::<Test>::get(auto_id_identifier).is_none());
     })
 }
+
+#[test]
+fn test_register_attested_auto_id() {
+    new_test_ext().execute_with(|| {
+        register_attested_auto_id();
+    })
+}
+
+#[test]
+fn test_revoke_attested_certificate() {
+    new_test_ext().execute_with(|| {
+        let auto_id_identifier = register_attested_auto_id();
+        let auto_id = AutoIds::<Test>::get(auto_id_identifier).unwrap();
+        assert!(!auto_id.certificate.is_revoked());
+        let signature = sign_preimage(auto_id_identifier.encode());
+        Pallet::<Test>::revoke_certificate(
+            RawOrigin::Signed(1).into(),
+            auto_id_identifier,
+            signature,
+        )
+        .unwrap();
+        let auto_id = AutoIds::<Test>::get(auto_id_identifier).unwrap();
+        assert!(auto_id.certificate.is_revoked());
+    })
+}
+
+#[test]
+fn test_deactivate_attested_auto_id() {
+    new_test_ext().execute_with(|| {
+        let auto_id_identifier = register_attested_auto_id();
+        let signature = sign_preimage(auto_id_identifier.encode());
+        Pallet::<Test>::deactivate_auto_id(
+            RawOrigin::Signed(1).into(),
+            auto_id_identifier,
+            signature,
+        )
+        .unwrap();
+        assert!(AutoIds::<Test>::get(auto_id_identifier).is_none());
+    })
+}
+
+#[test]
+fn test_normalize_serial_round_trips_der_and_raw_encodings() {
+    let raw: DerVec = vec![0x01, 0x02, 0x03].into();
+    let der_tlv: DerVec = {
+        let mut encoded = vec![0x02, 0x03];
+        encoded.extend_from_slice(raw.as_ref());
+        encoded.into()
+    };
+    let sign_padded: DerVec = {
+        let mut padded = vec![0x00];
+        padded.extend_from_slice(raw.as_ref());
+        padded.into()
+    };
+
+    assert_eq!(
+        crate::x509::normalize_serial(&raw),
+        crate::x509::normalize_serial(&der_tlv)
+    );
+    assert_eq!(
+        crate::x509::normalize_serial(&raw),
+        crate::x509::normalize_serial(&sign_padded)
+    );
+}
@@ -0,0 +1,302 @@
+//! X.509-specific parsing and verification helpers shared by registration and revocation.
+//!
+//! Actual ASN.1 parsing happens off-chain, behind the `sp-certificate-registry` host
+//! functions; this module only orchestrates those calls against on-chain state so the
+//! pallet itself never needs a DER parser compiled into the runtime.
+
+use crate::{
+    AutoId, CertificateAutoId, GeneralName, Identifier, NameConstraints, RegisterAutoIdX509,
+    RegisterError, Serial, X509Certificate,
+};
+use alloc::vec::Vec;
+use sp_certificate_registry::DerVec;
+
+/// Parses `req`, verifies its signature (against its own key for a `Root`, or against
+/// `issuer`'s key for a `Leaf`), enforces `BasicConstraints`/`KeyUsage`, and returns the
+/// `X509Certificate` to be stored.
+pub(crate) fn register(
+    _identifier: Identifier,
+    req: RegisterAutoIdX509,
+    issuer: Option<AutoId>,
+) -> Result<X509Certificate, RegisterError> {
+    match req {
+        RegisterAutoIdX509::Root {
+            certificate,
+            signature_algorithm,
+            signature,
+        } => {
+            let parsed = parse(&certificate)?;
+            verify(
+                &parsed.subject_public_key_info,
+                &signature_algorithm,
+                certificate.as_ref(),
+                &signature,
+            )?;
+
+            let basic_constraints = parsed.basic_constraints.unwrap_or_default();
+            if !basic_constraints.ca {
+                return Err(RegisterError::NotACertificateAuthority);
+            }
+            let key_cert_sign = parsed.key_usage.map(|ku| ku.key_cert_sign).unwrap_or(false);
+            if !key_cert_sign {
+                return Err(RegisterError::MissingKeyCertSign);
+            }
+
+            Ok(X509Certificate {
+                issuer_id: None,
+                serial: parsed.serial,
+                subject: parsed.subject,
+                certificate,
+                subject_public_key_info: parsed.subject_public_key_info,
+                is_ca: true,
+                key_cert_sign,
+                path_len_constraint: basic_constraints.path_len_constraint,
+                name_constraints: parsed.name_constraints.unwrap_or_default(),
+                revoked: false,
+            })
+        }
+        RegisterAutoIdX509::Leaf {
+            issuer_id,
+            certificate,
+            signature_algorithm,
+            signature,
+        } => {
+            let issuer = issuer.ok_or(RegisterError::UnknownIssuer)?;
+            let CertificateAutoId::X509(issuer_cert) = &issuer.certificate else {
+                return Err(RegisterError::IssuerNotX509);
+            };
+            // An issuer must itself be a CA entitled to sign (`keyCertSign`), regardless of
+            // whether the certificate being registered here is itself a further CA or an end
+            // entity; otherwise any already-registered leaf could be reused to sign more leaves.
+            if !issuer_cert.is_ca {
+                return Err(RegisterError::NotACertificateAuthority);
+            }
+            if !issuer_cert.key_cert_sign {
+                return Err(RegisterError::MissingKeyCertSign);
+            }
+
+            let parsed = parse(&certificate)?;
+            verify(
+                &issuer_cert.subject_public_key_info,
+                &signature_algorithm,
+                certificate.as_ref(),
+                &signature,
+            )?;
+
+            let basic_constraints = parsed.basic_constraints.unwrap_or_default();
+            let key_cert_sign = parsed.key_usage.map(|ku| ku.key_cert_sign).unwrap_or(false);
+            if basic_constraints.ca {
+                if issuer_cert.path_len_constraint == Some(0) {
+                    return Err(RegisterError::PathLengthExceeded);
+                }
+                if !key_cert_sign {
+                    return Err(RegisterError::MissingKeyCertSign);
+                }
+            }
+
+            let path_len_constraint = match issuer_cert.path_len_constraint {
+                Some(issuer_len) => {
+                    let allowed = issuer_len.saturating_sub(1);
+                    Some(
+                        basic_constraints
+                            .path_len_constraint
+                            .map_or(allowed, |own| own.min(allowed)),
+                    )
+                }
+                None => basic_constraints.path_len_constraint,
+            };
+
+            let mut leaf_names = parsed.subject_alt_names.clone();
+            leaf_names.push(GeneralName::DirectoryName(parsed.subject.as_ref().to_vec()));
+            if !names_satisfy_constraints(&issuer_cert.name_constraints, &leaf_names) {
+                return Err(RegisterError::NameConstraintViolation);
+            }
+
+            Ok(X509Certificate {
+                issuer_id: Some(issuer_id),
+                serial: parsed.serial,
+                subject: parsed.subject,
+                certificate,
+                subject_public_key_info: parsed.subject_public_key_info,
+                is_ca: basic_constraints.ca,
+                key_cert_sign,
+                path_len_constraint,
+                name_constraints: parsed.name_constraints.unwrap_or_default(),
+                revoked: false,
+            })
+        }
+    }
+}
+
+/// Parses a DER-encoded `TBSCertificate` via the host-function parser.
+pub(crate) fn parse(
+    certificate: &DerVec,
+) -> Result<sp_certificate_registry::ParsedCertificate, RegisterError> {
+    sp_certificate_registry::parse_certificate(certificate).ok_or(RegisterError::InvalidCertificate)
+}
+
+/// Verifies `signature` (produced with `signature_algorithm`) over `data`, against the given
+/// DER-encoded `SubjectPublicKeyInfo`, via the host-function verification path.
+pub(crate) fn verify(
+    subject_public_key_info: &DerVec,
+    signature_algorithm: &DerVec,
+    data: &[u8],
+    signature: &[u8],
+) -> Result<(), RegisterError> {
+    let valid = sp_certificate_registry::verify_signature(
+        subject_public_key_info,
+        signature_algorithm,
+        data,
+        signature,
+    )
+    .ok_or(RegisterError::InvalidSignature)?;
+
+    if valid {
+        Ok(())
+    } else {
+        Err(RegisterError::InvalidSignature)
+    }
+}
+
+/// A parsed CRL, with its revoked serials resolved down to the bytes we key
+/// `CertificateSerials` with.
+pub(crate) struct ParsedCrl {
+    pub(crate) issuer: DerVec,
+    pub(crate) this_update: subspace_runtime_primitives::Moment,
+    /// `nextUpdate` is OPTIONAL in `TBSCertList` (RFC 5280 §5.1.2.5); a CRL that omits it never
+    /// expires on its own and must not be rejected as out of its validity window.
+    pub(crate) next_update: Option<subspace_runtime_primitives::Moment>,
+    pub(crate) revoked_serials: alloc::vec::Vec<Serial>,
+}
+
+/// Parses a DER-encoded `TBSCertList` via the host-function parser, verifying its signature
+/// against `issuer`'s public key along the way.
+pub(crate) fn parse_and_verify_crl(
+    issuer: &X509Certificate,
+    tbs_cert_list: &DerVec,
+    signature: &crate::Signature,
+) -> Result<ParsedCrl, RegisterError> {
+    verify(
+        &issuer.subject_public_key_info,
+        &signature.signature_algorithm,
+        tbs_cert_list.as_ref(),
+        &signature.value,
+    )?;
+
+    let parsed = sp_certificate_registry::parse_crl(tbs_cert_list)
+        .ok_or(RegisterError::InvalidCertificate)?;
+
+    Ok(ParsedCrl {
+        issuer: parsed.issuer,
+        this_update: parsed.this_update,
+        next_update: parsed.next_update,
+        revoked_serials: parsed
+            .revoked_serials
+            .iter()
+            .map(normalize_serial)
+            .collect(),
+    })
+}
+
+/// Reduces a DER-encoded `CertificateSerialNumber` to its bare `INTEGER` content: strips the
+/// `02 <len>` tag/length header if present, and a leading all-zero sign-padding byte. Serials
+/// read back from a certificate (`parse`) and from a CRL entry (`parse_and_verify_crl`) must
+/// key `CertificateSerials` identically regardless of which of these forms the host-function
+/// parser happened to hand back, or a CRL's revocations silently fail to resolve to an AutoId.
+pub(crate) fn normalize_serial(serial: &Serial) -> Serial {
+    let bytes = serial.as_ref();
+    let content = match bytes {
+        [0x02, len, rest @ ..] if *len as usize == rest.len() => rest,
+        _ => bytes,
+    };
+    match content {
+        [0x00, rest @ ..] if rest.first().is_some_and(|b| *b < 0x80) => rest,
+        _ => content,
+    }
+    .to_vec()
+    .into()
+}
+
+/// Checks every name in `names` against `constraints`, per RFC 5280 §4.2.1.10: a name must
+/// match at least one permitted subtree of its own type (unless that type has no permitted
+/// subtrees, in which case it is unconstrained), and must match no excluded subtree.
+fn names_satisfy_constraints(constraints: &NameConstraints, names: &[GeneralName]) -> bool {
+    names.iter().all(|name| {
+        let same_type_permitted: Vec<&GeneralName> = constraints
+            .permitted
+            .iter()
+            .filter(|c| core::mem::discriminant(*c) == core::mem::discriminant(name))
+            .collect();
+        if !same_type_permitted.is_empty()
+            && !same_type_permitted.iter().any(|c| name_matches(name, c))
+        {
+            return false;
+        }
+
+        !constraints.excluded.iter().any(|c| {
+            core::mem::discriminant(c) == core::mem::discriminant(name) && name_matches(name, c)
+        })
+    })
+}
+
+fn name_matches(name: &GeneralName, constraint: &GeneralName) -> bool {
+    match (name, constraint) {
+        (GeneralName::DnsName(name), GeneralName::DnsName(constraint)) => {
+            dns_name_matches(name, constraint)
+        }
+        (GeneralName::DirectoryName(name), GeneralName::DirectoryName(constraint)) => {
+            rdn_sequence(name).starts_with(rdn_sequence(constraint))
+        }
+        (GeneralName::Rfc822Name(name), GeneralName::Rfc822Name(constraint)) => {
+            rfc822_name_matches(name, constraint)
+        }
+        _ => false,
+    }
+}
+
+/// Strips a `Name`'s outer DER `SEQUENCE` tag/length header, returning the concatenated RDN
+/// TLVs it contains. Each RDN is self-delimited by its own length, so the inner content of a
+/// DN with fewer RDNs is a true byte-prefix of one with more; the outer `SEQUENCE` length byte
+/// is not, since it grows with the whole DN and would falsely break containment (e.g. a
+/// one-RDN `name.starts_with(constraint)` test against a two-RDN DN with the same leading RDN).
+fn rdn_sequence(name: &[u8]) -> &[u8] {
+    match name {
+        [0x30, len, rest @ ..] if (*len as usize) < 0x80 => {
+            rest.get(..*len as usize).unwrap_or(rest)
+        }
+        [0x30, 0x81, len, rest @ ..] => rest.get(..*len as usize).unwrap_or(rest),
+        [0x30, 0x82, len_hi, len_lo, rest @ ..] => {
+            let len = ((*len_hi as usize) << 8) | *len_lo as usize;
+            rest.get(..len).unwrap_or(rest)
+        }
+        _ => name,
+    }
+}
+
+/// A dNSName constraint matches itself and any subdomain (`example.com` matches
+/// `foo.example.com`), but not unrelated names sharing a suffix (`notexample.com`).
+fn dns_name_matches(name: &[u8], constraint: &[u8]) -> bool {
+    if name.eq_ignore_ascii_case(constraint) {
+        return true;
+    }
+    name.len() > constraint.len()
+        && name[name.len() - constraint.len() - 1] == b'.'
+        && name[name.len() - constraint.len()..].eq_ignore_ascii_case(constraint)
+}
+
+/// An rfc822Name constraint is either a full mailbox, a hostname (matched like a dNSName
+/// against the part after `@`), or a `@domain` suffix.
+fn rfc822_name_matches(name: &[u8], constraint: &[u8]) -> bool {
+    if constraint.starts_with(b"@") {
+        return name
+            .to_ascii_lowercase()
+            .ends_with(&constraint.to_ascii_lowercase());
+    }
+    if constraint.contains(&b'@') {
+        return name.eq_ignore_ascii_case(constraint);
+    }
+    match name.iter().position(|&b| b == b'@') {
+        Some(at) => dns_name_matches(&name[at + 1..], constraint),
+        None => false,
+    }
+}